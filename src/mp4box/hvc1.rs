@@ -83,45 +83,19 @@ impl Mp4Box for Hvc1Box {
 
 impl<R: Read + Seek> ReadBox<&mut R> for Hvc1Box {
     fn read_box(reader: &mut R, size: u64) -> Result<Self> {
-        let start = box_start(reader)?;
+        let (start, fields, hvcc) = read_hvc_sample_entry(reader)?;
+        skip_bytes_to(reader, start + size)?;
 
-        reader.read_u32::<BigEndian>()?; // reserved
-        reader.read_u16::<BigEndian>()?; // reserved
-        let data_reference_index = reader.read_u16::<BigEndian>()?;
-
-        reader.read_u32::<BigEndian>()?; // pre-defined, reserved
-        reader.read_u64::<BigEndian>()?; // pre-defined
-        reader.read_u32::<BigEndian>()?; // pre-defined
-        let width = reader.read_u16::<BigEndian>()?;
-        let height = reader.read_u16::<BigEndian>()?;
-        let horizresolution = FixedPointU16::new_raw(reader.read_u32::<BigEndian>()?);
-        let vertresolution = FixedPointU16::new_raw(reader.read_u32::<BigEndian>()?);
-        reader.read_u32::<BigEndian>()?; // reserved
-        let frame_count = reader.read_u16::<BigEndian>()?;
-        skip_bytes(reader, 32)?; // compressorname
-        let depth = reader.read_u16::<BigEndian>()?;
-        reader.read_i16::<BigEndian>()?; // pre-defined
-
-        let header = BoxHeader::read(reader)?;
-        let BoxHeader { name, size: s } = header;
-        if name == BoxType::HvcCBox {
-            let hvcc = HvcCBox::read_box(reader, s)?;
-
-            skip_bytes_to(reader, start + size)?;
-
-            Ok(Hvc1Box {
-                data_reference_index,
-                width,
-                height,
-                horizresolution,
-                vertresolution,
-                frame_count,
-                depth,
-                hvcc,
-            })
-        } else {
-            Err(Error::InvalidData("hvcc not found"))
-        }
+        Ok(Hvc1Box {
+            data_reference_index: fields.data_reference_index,
+            width: fields.width,
+            height: fields.height,
+            horizresolution: fields.horizresolution,
+            vertresolution: fields.vertresolution,
+            frame_count: fields.frame_count,
+            depth: fields.depth,
+            hvcc,
+        })
     }
 }
 
@@ -130,23 +104,131 @@ impl<W: Write> WriteBox<&mut W> for Hvc1Box {
         let size = self.box_size();
         BoxHeader::new(self.box_type(), size).write(writer)?;
 
-        writer.write_u32::<BigEndian>(0)?; // reserved
-        writer.write_u16::<BigEndian>(0)?; // reserved
-        writer.write_u16::<BigEndian>(self.data_reference_index)?;
-
-        writer.write_u32::<BigEndian>(0)?; // pre-defined, reserved
-        writer.write_u64::<BigEndian>(0)?; // pre-defined
-        writer.write_u32::<BigEndian>(0)?; // pre-defined
-        writer.write_u16::<BigEndian>(self.width)?;
-        writer.write_u16::<BigEndian>(self.height)?;
-        writer.write_u32::<BigEndian>(self.horizresolution.raw_value())?;
-        writer.write_u32::<BigEndian>(self.vertresolution.raw_value())?;
-        writer.write_u32::<BigEndian>(0)?; // reserved
-        writer.write_u16::<BigEndian>(self.frame_count)?;
-        // skip compressorname
-        write_zeros(writer, 32)?;
-        writer.write_u16::<BigEndian>(self.depth)?;
-        writer.write_i16::<BigEndian>(-1)?; // pre-defined
+        write_hvc_sample_entry(writer, &HvcSampleEntryFields {
+            data_reference_index: self.data_reference_index,
+            width: self.width,
+            height: self.height,
+            horizresolution: self.horizresolution,
+            vertresolution: self.vertresolution,
+            frame_count: self.frame_count,
+            depth: self.depth,
+        })?;
+
+        self.hvcc.write_box(writer)?;
+
+        Ok(size)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hev1Box {
+    pub data_reference_index: u16,
+    pub width: u16,
+    pub height: u16,
+
+    #[serde(with = "value_u32")]
+    pub horizresolution: FixedPointU16,
+
+    #[serde(with = "value_u32")]
+    pub vertresolution: FixedPointU16,
+    pub frame_count: u16,
+    pub depth: u16,
+    pub hvcc: HvcCBox,
+}
+
+impl Default for Hev1Box {
+    fn default() -> Self {
+        Hev1Box {
+            data_reference_index: 0,
+            width: 0,
+            height: 0,
+            horizresolution: FixedPointU16::new(0x48),
+            vertresolution: FixedPointU16::new(0x48),
+            frame_count: 1,
+            depth: 0x0018,
+            hvcc: HvcCBox::default(),
+        }
+    }
+}
+
+impl Hev1Box {
+    pub fn new(config: &HvcConfig) -> Self {
+        Hev1Box {
+            data_reference_index: 1,
+            width: config.width,
+            height: config.height,
+            horizresolution: FixedPointU16::new(0x48),
+            vertresolution: FixedPointU16::new(0x48),
+            frame_count: 1,
+            depth: 0x0018,
+            hvcc: HvcCBox::new(config.video_param_sets.iter().map(|v| v.as_slice()).collect(),
+            config.seq_param_sets.iter().map(|v| v.as_slice()).collect(),
+            config.pic_param_sets.iter().map(|v| v.as_slice()).collect(),
+            config.supplementary_enhancement_information.iter().map(|v| v.as_slice()).collect()),
+        }
+    }
+
+    pub fn get_type(&self) -> BoxType {
+        BoxType::Hev1Box
+    }
+
+    pub fn get_size(&self) -> u64 {
+        HEADER_SIZE + 8 + 70 + self.hvcc.box_size()
+    }
+}
+
+impl Mp4Box for Hev1Box {
+    fn box_type(&self) -> BoxType {
+        return self.get_type();
+    }
+
+    fn box_size(&self) -> u64 {
+        return self.get_size();
+    }
+
+    fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self).unwrap())
+    }
+
+    fn summary(&self) -> Result<String> {
+        let s = format!("data_reference_index={} width={} height={} frame_count={}",
+            self.data_reference_index, self.width, self.height, self.frame_count);
+        Ok(s)
+    }
+}
+
+impl<R: Read + Seek> ReadBox<&mut R> for Hev1Box {
+    fn read_box(reader: &mut R, size: u64) -> Result<Self> {
+        let (start, fields, hvcc) = read_hvc_sample_entry(reader)?;
+        skip_bytes_to(reader, start + size)?;
+
+        Ok(Hev1Box {
+            data_reference_index: fields.data_reference_index,
+            width: fields.width,
+            height: fields.height,
+            horizresolution: fields.horizresolution,
+            vertresolution: fields.vertresolution,
+            frame_count: fields.frame_count,
+            depth: fields.depth,
+            hvcc,
+        })
+    }
+}
+
+impl<W: Write> WriteBox<&mut W> for Hev1Box {
+    fn write_box(&self, writer: &mut W) -> Result<u64> {
+        let size = self.box_size();
+        BoxHeader::new(self.box_type(), size).write(writer)?;
+
+        write_hvc_sample_entry(writer, &HvcSampleEntryFields {
+            data_reference_index: self.data_reference_index,
+            width: self.width,
+            height: self.height,
+            horizresolution: self.horizresolution,
+            vertresolution: self.vertresolution,
+            frame_count: self.frame_count,
+            depth: self.depth,
+        })?;
 
         self.hvcc.write_box(writer)?;
 
@@ -154,34 +236,177 @@ impl<W: Write> WriteBox<&mut W> for Hvc1Box {
     }
 }
 
+/// Fields shared by the `hvc1`/`hev1` visual sample entry layout, factored out so
+/// both box types can read/write the common header around the nested `hvcC`.
+struct HvcSampleEntryFields {
+    data_reference_index: u16,
+    width: u16,
+    height: u16,
+    horizresolution: FixedPointU16,
+    vertresolution: FixedPointU16,
+    frame_count: u16,
+    depth: u16,
+}
+
+fn read_hvc_sample_entry<R: Read + Seek>(reader: &mut R) -> Result<(u64, HvcSampleEntryFields, HvcCBox)> {
+    let start = box_start(reader)?;
+
+    reader.read_u32::<BigEndian>()?; // reserved
+    reader.read_u16::<BigEndian>()?; // reserved
+    let data_reference_index = reader.read_u16::<BigEndian>()?;
+
+    reader.read_u32::<BigEndian>()?; // pre-defined, reserved
+    reader.read_u64::<BigEndian>()?; // pre-defined
+    reader.read_u32::<BigEndian>()?; // pre-defined
+    let width = reader.read_u16::<BigEndian>()?;
+    let height = reader.read_u16::<BigEndian>()?;
+    let horizresolution = FixedPointU16::new_raw(reader.read_u32::<BigEndian>()?);
+    let vertresolution = FixedPointU16::new_raw(reader.read_u32::<BigEndian>()?);
+    reader.read_u32::<BigEndian>()?; // reserved
+    let frame_count = reader.read_u16::<BigEndian>()?;
+    skip_bytes(reader, 32)?; // compressorname
+    let depth = reader.read_u16::<BigEndian>()?;
+    reader.read_i16::<BigEndian>()?; // pre-defined
+
+    let header = BoxHeader::read(reader)?;
+    let BoxHeader { name, size: s } = header;
+    if name == BoxType::HvcCBox {
+        let hvcc = HvcCBox::read_box(reader, s)?;
+        Ok((start, HvcSampleEntryFields {
+            data_reference_index,
+            width,
+            height,
+            horizresolution,
+            vertresolution,
+            frame_count,
+            depth,
+        }, hvcc))
+    } else {
+        Err(Error::InvalidData("hvcc not found"))
+    }
+}
+
+fn write_hvc_sample_entry<W: Write>(writer: &mut W, fields: &HvcSampleEntryFields) -> Result<()> {
+    writer.write_u32::<BigEndian>(0)?; // reserved
+    writer.write_u16::<BigEndian>(0)?; // reserved
+    writer.write_u16::<BigEndian>(fields.data_reference_index)?;
+
+    writer.write_u32::<BigEndian>(0)?; // pre-defined, reserved
+    writer.write_u64::<BigEndian>(0)?; // pre-defined
+    writer.write_u32::<BigEndian>(0)?; // pre-defined
+    writer.write_u16::<BigEndian>(fields.width)?;
+    writer.write_u16::<BigEndian>(fields.height)?;
+    writer.write_u32::<BigEndian>(fields.horizresolution.raw_value())?;
+    writer.write_u32::<BigEndian>(fields.vertresolution.raw_value())?;
+    writer.write_u32::<BigEndian>(0)?; // reserved
+    writer.write_u16::<BigEndian>(fields.frame_count)?;
+    // skip compressorname
+    write_zeros(writer, 32)?;
+    writer.write_u16::<BigEndian>(fields.depth)?;
+    writer.write_i16::<BigEndian>(-1)?; // pre-defined
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct HvcCBox {
-    pub general_configuration: [u8; 12],
+    pub general_profile_space: u8,
+    pub general_tier_flag: bool,
+    pub general_profile_idc: u8,
+    pub general_profile_compatibility_flags: u32,
+    pub general_constraint_indicator_flags: u64,
+    pub general_level_idc: u8,
     pub num_temporal_layer: u8,
     pub chroma_idc: u8,
     pub bit_depth_luma_minus8: u8,
     pub bit_depth_chroma_minus8: u8,
     pub temporal_id_nested: bool,
-    pub video_parameter_sets: Vec<NalUnit>,
-    pub sequence_parameter_sets: Vec<NalUnit>,
-    pub picture_parameter_sets: Vec<NalUnit>,
-    pub supplementary_enhancement_information: Vec<NalUnit>,
+    pub length_size_minus_one: u8,
+    pub nal_arrays: Vec<HvcNalArray>,
 }
 
 impl HvcCBox {
     pub fn new(vps: Vec<&[u8]>, sps: Vec<&[u8]>, pps: Vec<&[u8]>, sei: Vec<&[u8]>) -> Self {
+        let mut nal_arrays = Vec::new();
+        if !vps.is_empty() {
+            nal_arrays.push(HvcNalArray::new(32, vps));
+        }
+        if !sps.is_empty() {
+            nal_arrays.push(HvcNalArray::new(33, sps));
+        }
+        if !pps.is_empty() {
+            nal_arrays.push(HvcNalArray::new(34, pps));
+        }
+        if !sei.is_empty() {
+            nal_arrays.push(HvcNalArray::new(39, sei));
+        }
         Self {
-            general_configuration: [0; 12],
+            general_profile_space: 0,
+            general_tier_flag: false,
+            general_profile_idc: 0,
+            general_profile_compatibility_flags: 0,
+            general_constraint_indicator_flags: 0,
+            general_level_idc: 0,
             num_temporal_layer: 0,
             chroma_idc: 0,
             bit_depth_luma_minus8: 0,
             bit_depth_chroma_minus8: 0,
             temporal_id_nested: false,
-            video_parameter_sets: vps.into_iter().map(|v| NalUnit::from(v)).collect(),
-            sequence_parameter_sets: sps.into_iter().map(|v| NalUnit::from(v)).collect(),
-            picture_parameter_sets: pps.into_iter().map(|v| NalUnit::from(v)).collect(),
-            supplementary_enhancement_information: sei.into_iter().map(|v| NalUnit::from(v)).collect()
+            length_size_minus_one: 3,
+            nal_arrays,
+        }
+    }
+
+    /// Number of bytes used by the length prefix of NAL units carried in the
+    /// samples (mdat), as configured by `length_size_minus_one`.
+    pub fn nal_length_size(&self) -> u8 {
+        self.length_size_minus_one + 1
+    }
+
+    fn nalus_of_type(&self, nal_unit_type: u8) -> Vec<&NalUnit> {
+        self.nal_arrays.iter()
+            .filter(|array| array.nal_unit_type == nal_unit_type)
+            .flat_map(|array| array.nalus.iter())
+            .collect()
+    }
+
+    /// Video parameter sets (NAL unit type 32), across all arrays of that type.
+    pub fn vps(&self) -> Vec<&NalUnit> {
+        self.nalus_of_type(32)
+    }
+
+    /// Sequence parameter sets (NAL unit type 33), across all arrays of that type.
+    pub fn sps(&self) -> Vec<&NalUnit> {
+        self.nalus_of_type(33)
+    }
+
+    /// Picture parameter sets (NAL unit type 34), across all arrays of that type.
+    pub fn pps(&self) -> Vec<&NalUnit> {
+        self.nalus_of_type(34)
+    }
+
+    /// Supplementary enhancement information (NAL unit type 39, prefix SEI),
+    /// across all arrays of that type.
+    pub fn sei(&self) -> Vec<&NalUnit> {
+        self.nalus_of_type(39)
+    }
+
+    /// Emits the stored parameter sets as an Annex B byte stream: VPS, then
+    /// SPS, then PPS, each prefixed with a `00 00 00 01` start code. Suitable
+    /// for feeding straight into decoders/muxers that expect Annex B.
+    pub fn to_annex_b(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for nalu in self.vps().into_iter().chain(self.sps()).chain(self.pps()) {
+            bytes.extend_from_slice(&nalu.to_annex_b());
         }
+        bytes
+    }
+
+    /// Ingests an Annex B byte stream and splits it into length-prefixed
+    /// `NalUnit`s, one per NAL delimited by a 3- or 4-byte start code.
+    /// Emulation-prevention bytes are left intact.
+    pub fn from_annex_b(bytes: &[u8]) -> Vec<NalUnit> {
+        NalUnit::split_annex_b(bytes)
     }
 }
 
@@ -192,28 +417,10 @@ impl Mp4Box for HvcCBox {
 
     fn box_size(&self) -> u64 {
         let mut size = HEADER_SIZE + 23;
-        if self.video_parameter_sets.len() > 0 {
+        for array in self.nal_arrays.iter() {
             size += 3;
-            for vps in self.video_parameter_sets.iter() {
-                size += vps.size() as u64;
-            }
-        }
-        if self.sequence_parameter_sets.len() > 0 {
-            size += 3;
-            for sps in self.sequence_parameter_sets.iter() {
-                size += sps.size() as u64;
-            }
-        }
-        if self.picture_parameter_sets.len() > 0 {
-            size += 3;
-            for pps in self.picture_parameter_sets.iter() {
-                size += pps.size() as u64;
-            }
-        }
-        if self.supplementary_enhancement_information.len() > 0 {
-            size += 3;
-            for sei in self.supplementary_enhancement_information.iter() {
-                size += sei.size() as u64;
+            for nalu in array.nalus.iter() {
+                size += nalu.size() as u64;
             }
         }
         size
@@ -224,8 +431,16 @@ impl Mp4Box for HvcCBox {
     }
 
     fn summary(&self) -> Result<String> {
-        let s = format!("chroma_idc={}",
-            self.chroma_idc);
+        let s = format!(
+            "chroma_idc={} general_profile_space={} general_tier_flag={} general_profile_idc={} general_profile_compatibility_flags={:#010x} general_constraint_indicator_flags={:#014x} general_level_idc={}",
+            self.chroma_idc,
+            self.general_profile_space,
+            self.general_tier_flag,
+            self.general_profile_idc,
+            self.general_profile_compatibility_flags,
+            self.general_constraint_indicator_flags,
+            self.general_level_idc,
+        );
         Ok(s)
     }
 }
@@ -234,8 +449,16 @@ impl<R: Read + Seek> ReadBox<&mut R> for HvcCBox {
     fn read_box(reader: &mut R, size: u64) -> Result<Self> {
         let start = box_start(reader)?;
         reader.read_u8()?; // 0x01
-        let mut general_configuration = [0u8; 12];
-        reader.read_exact(&mut general_configuration[..])?;
+        let general_byte = reader.read_u8()?;
+        let general_profile_space = general_byte >> 6;
+        let general_tier_flag = (general_byte & 0x20) == 0x20;
+        let general_profile_idc = general_byte & 0x1F;
+        let general_profile_compatibility_flags = reader.read_u32::<BigEndian>()?;
+        let mut constraint_bytes = [0u8; 6];
+        reader.read_exact(&mut constraint_bytes[..])?;
+        let general_constraint_indicator_flags = constraint_bytes.iter()
+            .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        let general_level_idc = reader.read_u8()?;
         reader.read_u16::<BigEndian>()?; //0xF000 min spatial segmentation
         reader.read_u8()?; // 0xFC parallelism type since segmentation
         let chroma_idc = reader.read_u8()? & 0x03;
@@ -245,42 +468,37 @@ impl<R: Read + Seek> ReadBox<&mut R> for HvcCBox {
         let stc = reader.read_u8()?;
         let num_temporal_layer = stc >> 3;
         let temporal_id_nested = (stc & 0x04) == 0x04;
-        let num_nals = reader.read_u8()?;
-        let mut video_parameter_sets = Vec::new();
-        let mut sequence_parameter_sets = Vec::new();
-        let mut picture_parameter_sets = Vec::new();
-        let mut supplementary_enhancement_information = Vec::new();
-
-        let mut i_nal = 0;
-        while i_nal < num_nals {
-            let sub_nal_type = reader.read_u8()?;
-            let sub_nal_num = reader.read_u16::<BigEndian>()?;
-            for _ in 0..sub_nal_num {
-                let nal_unit = NalUnit::read(reader)?;
-                match sub_nal_type {
-                    32 => video_parameter_sets.push(nal_unit),
-                    33 => sequence_parameter_sets.push(nal_unit),
-                    34 => picture_parameter_sets.push(nal_unit),
-                    39 => supplementary_enhancement_information.push(nal_unit),
-                    _ => ()
-                }
-                i_nal += 1;
+        let length_size_minus_one = stc & 0x03;
+        let num_arrays = reader.read_u8()?;
+        let mut nal_arrays = Vec::with_capacity(num_arrays as usize);
+        for _ in 0..num_arrays {
+            let array_byte = reader.read_u8()?;
+            let completeness = (array_byte & 0x80) == 0x80;
+            let nal_unit_type = array_byte & 0x3F;
+            let num_nalus = reader.read_u16::<BigEndian>()?;
+            let mut nalus = Vec::with_capacity(num_nalus as usize);
+            for _ in 0..num_nalus {
+                nalus.push(NalUnit::read(reader)?);
             }
+            nal_arrays.push(HvcNalArray { completeness, nal_unit_type, nalus });
         }
 
         skip_bytes_to(reader, start + size)?;
 
         Ok(HvcCBox {
-            general_configuration,
+            general_profile_space,
+            general_tier_flag,
+            general_profile_idc,
+            general_profile_compatibility_flags,
+            general_constraint_indicator_flags,
+            general_level_idc,
             chroma_idc,
             bit_depth_luma_minus8,
             bit_depth_chroma_minus8,
             num_temporal_layer,
             temporal_id_nested,
-            video_parameter_sets,
-            sequence_parameter_sets,
-            picture_parameter_sets,
-            supplementary_enhancement_information
+            length_size_minus_one,
+            nal_arrays,
         })
     }
 }
@@ -290,6 +508,12 @@ impl<W: Write> WriteBox<&mut W> for HvcCBox {
         let size = self.box_size();
         BoxHeader::new(self.box_type(), size).write(writer)?;
         writer.write_u8(0x01)?;
+        let tier_flag = if self.general_tier_flag { 1 } else { 0 };
+        writer.write_u8((self.general_profile_space << 6) | (tier_flag << 5) | (self.general_profile_idc & 0x1F))?;
+        writer.write_u32::<BigEndian>(self.general_profile_compatibility_flags)?;
+        let constraint_bytes = self.general_constraint_indicator_flags.to_be_bytes();
+        writer.write(&constraint_bytes[2..8])?; // low 48 bits
+        writer.write_u8(self.general_level_idc)?;
         writer.write_u16::<BigEndian>(0xF000)?;
         writer.write_u8(0xFC)?;
         writer.write_u8(0xFC | (self.chroma_idc & 0x03))?;
@@ -297,41 +521,37 @@ impl<W: Write> WriteBox<&mut W> for HvcCBox {
         writer.write_u8(0xF8 | (self.bit_depth_chroma_minus8 & 0x07))?;
         writer.write_u16::<BigEndian>(0x0000)?; // framerate
         let temporal_id_nested = if self.temporal_id_nested {1} else {0};
-        writer.write_u8(((self.num_temporal_layer & 0x07) << 3) | ((temporal_id_nested << 2) | 0x03))?;
-        writer.write_u8((self.video_parameter_sets.len() + self.sequence_parameter_sets.len()
-            + self.picture_parameter_sets.len() + self.supplementary_enhancement_information.len()) as u8)?;
-        if self.video_parameter_sets.len() > 0 {
-            writer.write_u8(32)?;
-            writer.write_u16::<BigEndian>(self.video_parameter_sets.len() as u16)?;
-            for vps in self.video_parameter_sets.iter() {
-                vps.write(writer)?;
-            }
-        }
-        if self.sequence_parameter_sets.len() > 0 {
-            writer.write_u8(33)?;
-            writer.write_u16::<BigEndian>(self.sequence_parameter_sets.len() as u16)?;
-            for sps in self.sequence_parameter_sets.iter() {
-                sps.write(writer)?;
-            }
-        }
-        if self.picture_parameter_sets.len() > 0 {
-            writer.write_u8(34)?;
-            writer.write_u16::<BigEndian>(self.picture_parameter_sets.len() as u16)?;
-            for pps in self.picture_parameter_sets.iter() {
-                pps.write(writer)?;
-            }
-        }
-        if self.supplementary_enhancement_information.len() > 0 {
-            writer.write_u8(39)?;
-            writer.write_u16::<BigEndian>(self.supplementary_enhancement_information.len() as u16)?;
-            for sei in self.supplementary_enhancement_information.iter() {
-                sei.write(writer)?;
+        writer.write_u8(((self.num_temporal_layer & 0x07) << 3) | (temporal_id_nested << 2) | (self.length_size_minus_one & 0x03))?;
+        writer.write_u8(self.nal_arrays.len() as u8)?;
+        for array in self.nal_arrays.iter() {
+            let completeness = if array.completeness { 0x80 } else { 0x00 };
+            writer.write_u8(completeness | (array.nal_unit_type & 0x3F))?;
+            writer.write_u16::<BigEndian>(array.nalus.len() as u16)?;
+            for nalu in array.nalus.iter() {
+                nalu.write(writer)?;
             }
         }
         Ok(size)
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HvcNalArray {
+    pub completeness: bool,
+    pub nal_unit_type: u8,
+    pub nalus: Vec<NalUnit>,
+}
+
+impl HvcNalArray {
+    fn new(nal_unit_type: u8, nalus: Vec<&[u8]>) -> Self {
+        Self {
+            completeness: true,
+            nal_unit_type,
+            nalus: nalus.into_iter().map(NalUnit::from).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default, Serialize)]
 pub struct NalUnit {
     pub bytes: Vec<u8>,
@@ -353,7 +573,7 @@ impl NalUnit {
     fn read<R: Read + Seek>(reader: &mut R) -> Result<Self> {
         let length = reader.read_u16::<BigEndian>()? as usize;
         let mut bytes = vec![0u8; length];
-        reader.read(&mut bytes)?;
+        reader.read_exact(&mut bytes)?;
         Ok(NalUnit { bytes })
     }
 
@@ -362,6 +582,75 @@ impl NalUnit {
         writer.write(&self.bytes)?;
         Ok(self.size() as u64)
     }
+
+    /// Reads a NAL unit prefixed with a `length_size`-byte big-endian length
+    /// field (1, 2 or 4 bytes), as used for the sample data referenced by
+    /// `HvcCBox::nal_length_size()`. The parameter-set arrays in hvcC itself
+    /// always use a 2-byte length and should keep using [`NalUnit::read`].
+    pub fn read_sized<R: Read + Seek>(reader: &mut R, length_size: u8) -> Result<Self> {
+        let length = match length_size {
+            1 => reader.read_u8()? as usize,
+            2 => reader.read_u16::<BigEndian>()? as usize,
+            4 => reader.read_u32::<BigEndian>()? as usize,
+            _ => return Err(Error::InvalidData("unsupported NAL length size")),
+        };
+        let mut bytes = vec![0u8; length];
+        reader.read_exact(&mut bytes)?;
+        Ok(NalUnit { bytes })
+    }
+
+    /// Writes this NAL unit with a `length_size`-byte big-endian length field.
+    /// Counterpart to [`NalUnit::read_sized`].
+    pub fn write_sized<W: Write>(&self, writer: &mut W, length_size: u8) -> Result<u64> {
+        match length_size {
+            1 => writer.write_u8(self.bytes.len() as u8)?,
+            2 => writer.write_u16::<BigEndian>(self.bytes.len() as u16)?,
+            4 => writer.write_u32::<BigEndian>(self.bytes.len() as u32)?,
+            _ => return Err(Error::InvalidData("unsupported NAL length size")),
+        }
+        writer.write(&self.bytes)?;
+        Ok(length_size as u64 + self.bytes.len() as u64)
+    }
+
+    /// Returns this NAL unit as an Annex B byte sequence: a `00 00 00 01`
+    /// start code followed by the raw NAL bytes.
+    pub fn to_annex_b(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.bytes.len());
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x01]);
+        bytes.extend_from_slice(&self.bytes);
+        bytes
+    }
+
+    /// Splits an Annex B byte stream into individual `NalUnit`s by scanning
+    /// for 3- and 4-byte start codes. Emulation-prevention bytes within each
+    /// NAL are left intact.
+    fn split_annex_b(bytes: &[u8]) -> Vec<NalUnit> {
+        // (start code position, NAL content start) for each start code found,
+        // preferring the 4-byte code over the 3-byte code it contains.
+        let mut start_codes = Vec::new();
+        let mut i = 0;
+        while i + 2 < bytes.len() {
+            if bytes[i] == 0x00 && bytes[i + 1] == 0x00 && i + 3 < bytes.len()
+                && bytes[i + 2] == 0x00 && bytes[i + 3] == 0x01 {
+                start_codes.push((i, i + 4));
+                i += 4;
+            } else if bytes[i] == 0x00 && bytes[i + 1] == 0x00 && bytes[i + 2] == 0x01 {
+                start_codes.push((i, i + 3));
+                i += 3;
+            } else {
+                i += 1;
+            }
+        }
+
+        let mut nalus = Vec::new();
+        for (idx, &(_, content_start)) in start_codes.iter().enumerate() {
+            let content_end = start_codes.get(idx + 1).map(|&(start, _)| start).unwrap_or(bytes.len());
+            if content_start < content_end {
+                nalus.push(NalUnit { bytes: bytes[content_start..content_end].to_vec() });
+            }
+        }
+        nalus
+    }
 }
 
 // #[cfg(test)]
@@ -0,0 +1,37 @@
+pub mod hvc1;
+pub mod stsd;
+
+macro_rules! boxtype {
+    ($( $name:ident => $value:expr ),*) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        pub enum BoxType {
+            $( $name, )*
+            UnknownBox(u32),
+        }
+
+        impl From<u32> for BoxType {
+            fn from(t: u32) -> BoxType {
+                match t {
+                    $( $value => BoxType::$name, )*
+                    _ => BoxType::UnknownBox(t),
+                }
+            }
+        }
+
+        impl From<BoxType> for u32 {
+            fn from(b: BoxType) -> u32 {
+                match b {
+                    $( BoxType::$name => $value, )*
+                    BoxType::UnknownBox(t) => t,
+                }
+            }
+        }
+    }
+}
+
+boxtype! {
+    HvcCBox => 0x68766343,
+    Hvc1Box => 0x68766331,
+    Hev1Box => 0x68657631,
+    StsdBox => 0x73747364
+}
@@ -0,0 +1,113 @@
+use std::io::{Read, Seek, Write};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use serde::Serialize;
+
+use crate::mp4box::*;
+use crate::mp4box::hvc1::{Hev1Box, Hvc1Box};
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct StsdBox {
+    pub version: u8,
+    pub flags: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hvc1: Option<Hvc1Box>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hev1: Option<Hev1Box>,
+}
+
+impl StsdBox {
+    pub fn get_type(&self) -> BoxType {
+        BoxType::StsdBox
+    }
+
+    pub fn get_size(&self) -> u64 {
+        let mut size = HEADER_SIZE + 8;
+        if let Some(hvc1) = &self.hvc1 {
+            size += hvc1.box_size();
+        }
+        if let Some(hev1) = &self.hev1 {
+            size += hev1.box_size();
+        }
+        size
+    }
+}
+
+impl Mp4Box for StsdBox {
+    fn box_type(&self) -> BoxType {
+        self.get_type()
+    }
+
+    fn box_size(&self) -> u64 {
+        self.get_size()
+    }
+
+    fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self).unwrap())
+    }
+
+    fn summary(&self) -> Result<String> {
+        let s = format!("version={} flags={}", self.version, self.flags);
+        Ok(s)
+    }
+}
+
+impl<R: Read + Seek> ReadBox<&mut R> for StsdBox {
+    fn read_box(reader: &mut R, size: u64) -> Result<Self> {
+        let start = box_start(reader)?;
+
+        let version = reader.read_u8()?;
+        let flags = reader.read_u24::<BigEndian>()?;
+        let entry_count = reader.read_u32::<BigEndian>()?;
+
+        let mut hvc1 = None;
+        let mut hev1 = None;
+
+        for _ in 0..entry_count {
+            let header = BoxHeader::read(reader)?;
+            let BoxHeader { name, size: s } = header;
+            match name {
+                BoxType::Hvc1Box => {
+                    hvc1 = Some(Hvc1Box::read_box(reader, s)?);
+                }
+                BoxType::Hev1Box => {
+                    hev1 = Some(Hev1Box::read_box(reader, s)?);
+                }
+                _ => {
+                    skip_bytes(reader, s - HEADER_SIZE)?;
+                }
+            }
+        }
+
+        skip_bytes_to(reader, start + size)?;
+
+        Ok(StsdBox {
+            version,
+            flags,
+            hvc1,
+            hev1,
+        })
+    }
+}
+
+impl<W: Write> WriteBox<&mut W> for StsdBox {
+    fn write_box(&self, writer: &mut W) -> Result<u64> {
+        let size = self.box_size();
+        BoxHeader::new(self.box_type(), size).write(writer)?;
+
+        writer.write_u8(self.version)?;
+        writer.write_u24::<BigEndian>(self.flags)?;
+        let entry_count = self.hvc1.is_some() as u32 + self.hev1.is_some() as u32;
+        writer.write_u32::<BigEndian>(entry_count)?;
+
+        if let Some(hvc1) = &self.hvc1 {
+            hvc1.write_box(writer)?;
+        }
+        if let Some(hev1) = &self.hev1 {
+            hev1.write_box(writer)?;
+        }
+
+        Ok(size)
+    }
+}